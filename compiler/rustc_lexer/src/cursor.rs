@@ -0,0 +1,147 @@
+//! Peekable iterator over a char sequence.
+//!
+//! Next characters can be peeked via `first` method,
+//! and position can be shifted forward via `bump` method.
+
+use std::str::Chars;
+
+pub(crate) const EOF_CHAR: char = '\0';
+
+pub(crate) struct Cursor<'a> {
+    len_remaining: usize,
+    /// Iterator over chars. Slightly faster than a &str.
+    chars: Chars<'a>,
+    #[cfg(debug_assertions)]
+    prev: char,
+}
+
+impl<'a> Cursor<'a> {
+    pub(crate) fn new(input: &'a str) -> Cursor<'a> {
+        Cursor {
+            len_remaining: input.len(),
+            chars: input.chars(),
+            #[cfg(debug_assertions)]
+            prev: EOF_CHAR,
+        }
+    }
+
+    /// Returns the last eaten symbol (or `'\0'` in release builds).
+    pub(crate) fn prev(&self) -> char {
+        #[cfg(debug_assertions)]
+        {
+            self.prev
+        }
+
+        #[cfg(not(debug_assertions))]
+        {
+            EOF_CHAR
+        }
+    }
+
+    /// Peeks the next symbol from the input stream without consuming it.
+    pub(crate) fn first(&self) -> char {
+        self.chars.clone().next().unwrap_or(EOF_CHAR)
+    }
+
+    /// Peeks the second symbol from the input stream without consuming it.
+    pub(crate) fn second(&self) -> char {
+        let mut iter = self.chars.clone();
+        iter.next();
+        iter.next().unwrap_or(EOF_CHAR)
+    }
+
+    /// Checks if there is nothing more to consume.
+    pub(crate) fn is_eof(&self) -> bool {
+        self.chars.as_str().is_empty()
+    }
+
+    /// Returns the number of bytes consumed so far, since the start of
+    /// the current token (i.e. since the last `reset_len_consumed` call).
+    pub(crate) fn len_consumed(&self) -> usize {
+        self.len_remaining - self.chars.as_str().len()
+    }
+
+    /// Resets the counter used by `len_consumed`, marking the cursor's
+    /// current position as the start of the next token.
+    pub(crate) fn reset_len_consumed(&mut self) {
+        self.len_remaining = self.chars.as_str().len();
+    }
+
+    /// Moves to the next character.
+    pub(crate) fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+
+        #[cfg(debug_assertions)]
+        {
+            self.prev = c;
+        }
+
+        Some(c)
+    }
+
+    /// Eats symbols while predicate returns true or until the end of file is reached.
+    pub(crate) fn eat_while(&mut self, mut predicate: impl FnMut(char) -> bool) {
+        while predicate(self.first()) && !self.is_eof() {
+            self.bump();
+        }
+    }
+
+    /// Eats the identifier suffix that may follow a numeric or char/string literal.
+    pub(crate) fn eat_literal_suffix(&mut self) {
+        self.eat_identifier();
+    }
+
+    /// Eats a full identifier, if it starts with an identifier-start character.
+    pub(crate) fn eat_identifier(&mut self) {
+        if !crate::is_id_start(self.first()) {
+            return;
+        }
+        self.bump();
+        self.eat_while(crate::is_id_continue);
+    }
+
+    /// Eats a decimal digit run, returning true if at least one was eaten.
+    pub(crate) fn eat_decimal_digits(&mut self) -> bool {
+        let mut has_digits = false;
+        loop {
+            match self.first() {
+                '_' => {
+                    self.bump();
+                }
+                '0'..='9' => {
+                    has_digits = true;
+                    self.bump();
+                }
+                _ => break,
+            }
+        }
+        has_digits
+    }
+
+    /// Eats a hexadecimal digit run, returning true if at least one was eaten.
+    pub(crate) fn eat_hexadecimal_digits(&mut self) -> bool {
+        let mut has_digits = false;
+        loop {
+            match self.first() {
+                '_' => {
+                    self.bump();
+                }
+                '0'..='9' | 'a'..='f' | 'A'..='F' => {
+                    has_digits = true;
+                    self.bump();
+                }
+                _ => break,
+            }
+        }
+        has_digits
+    }
+
+    /// Eats the float exponent. Assumes the `e`/`E` has already been eaten.
+    pub(crate) fn eat_float_exponent(&mut self) -> bool {
+        debug_assert!(self.prev() == 'e' || self.prev() == 'E');
+        if self.first() == '-' || self.first() == '+' {
+            self.bump();
+        }
+        self.eat_decimal_digits()
+    }
+}
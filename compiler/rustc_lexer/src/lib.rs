@@ -0,0 +1,293 @@
+//! Low-level Rust lexer.
+//!
+//! The idea with `rustc_lexer` is to make a reusable library,
+//! by separating out pure lexing and rustc-specific concerns, like spans,
+//! error reporting, and interning. So, rustc_lexer operates directly on `&str`,
+//! produces simple tokens which are a pair of type-tag and a bit of original text,
+//! and does not report errors, instead storing them as flags on the token.
+//!
+//! Tokens produced by this lexer are not yet ready for parsing the Rust syntax.
+//! For that see [`rustc_parse::lexer`], which converts this basic token stream
+//! into wide tokens used by actual parser.
+//!
+//! The purpose of this crate is to convert raw sources into a labeled sequence
+//! of well-known token types, so building an actual Rust token stream will
+//! be easier.
+
+mod cursor;
+pub mod literals;
+pub mod unescape;
+
+use crate::cursor::Cursor;
+use crate::literals::{LiteralKind, StringPrefix};
+
+pub use crate::literals::{Base, RawStrContents, RawStrError};
+
+/// Parsed token.
+/// It doesn't contain information about data that has been parsed,
+/// only the type of the token and its size.
+#[derive(Debug)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub len: usize,
+}
+
+impl Token {
+    fn new(kind: TokenKind, len: usize) -> Token {
+        Token { kind, len }
+    }
+}
+
+/// Enum representing common lexeme types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    /// A line comment, e.g. `// comment`.
+    LineComment,
+    /// A block comment, e.g. `/* block comment */`.
+    ///
+    /// Block comments can be recursive, so a sequence like `/* /* */`
+    /// will not be considered terminated and will result in a parsing error.
+    BlockComment { terminated: bool },
+    /// Any whitespace character sequence.
+    Whitespace,
+    /// An identifier or keyword, e.g. `ident` or `continue`.
+    Ident,
+    /// A raw identifier, e.g. `r#ident`.
+    RawIdent,
+    /// Literal, e.g. `12u8`, `1.0e-40`, `b"123"`, `c"abc"`.
+    Literal { kind: LiteralKind, suffix_start: usize },
+    /// `'a`
+    Lifetime { starts_with_number: bool },
+    /// `;`
+    Semi,
+    /// `,`
+    Comma,
+    /// `.`
+    Dot,
+    /// `(`
+    OpenParen,
+    /// `)`
+    CloseParen,
+    /// `{`
+    OpenBrace,
+    /// `}`
+    CloseBrace,
+    /// `[`
+    OpenBracket,
+    /// `]`
+    CloseBracket,
+    /// `@`
+    At,
+    /// `#`
+    Pound,
+    /// `~`
+    Tilde,
+    /// `?`
+    Question,
+    /// `:`
+    Colon,
+    /// `$`
+    Dollar,
+    /// `=`
+    Eq,
+    /// `!`
+    Bang,
+    /// `<`
+    Lt,
+    /// `>`
+    Gt,
+    /// `-`
+    Minus,
+    /// `&`
+    And,
+    /// `|`
+    Or,
+    /// `+`
+    Plus,
+    /// `*`
+    Star,
+    /// `/`
+    Slash,
+    /// `^`
+    Caret,
+    /// `%`
+    Percent,
+    /// Unknown token, not expected by the lexer, e.g. "№"
+    Unknown,
+}
+
+/// True if `c` is valid as a first character of an identifier.
+pub fn is_id_start(c: char) -> bool {
+    c == '_' || c.is_alphabetic()
+}
+
+/// True if `c` is valid as a non-first character of an identifier.
+pub fn is_id_continue(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// The passed string is lexically an identifier.
+pub fn is_ident(string: &str) -> bool {
+    let mut chars = string.chars();
+    if let Some(start) = chars.next() { is_id_start(start) && chars.all(is_id_continue) } else { false }
+}
+
+/// Creates an iterator that produces tokens from the input string.
+pub fn tokenize(input: &str) -> impl Iterator<Item = Token> + '_ {
+    let mut cursor = Cursor::new(input);
+    std::iter::from_fn(move || {
+        if cursor.is_eof() { None } else { Some(cursor.advance_token()) }
+    })
+}
+
+impl Cursor<'_> {
+    /// Parses a token from the input string.
+    fn advance_token(&mut self) -> Token {
+        let first_char = self.bump().unwrap();
+        let token_kind = match first_char {
+            '/' if self.first() == '/' => self.line_comment(),
+            '/' if self.first() == '*' => self.block_comment(),
+            c if c.is_whitespace() => self.whitespace(),
+
+            'r' if self.first() == '"' || self.first() == '#' => self.raw_ident_or_string(),
+            // Only a `b`/`c` prefix if followed by what `c_or_byte_string`
+            // knows how to dispatch on; otherwise this is just an identifier
+            // that happens to start with `b`/`c` (e.g. `brown`, `count`).
+            'b' if self.is_byte_or_c_string_start() => self.c_or_byte_string(StringPrefix::Byte),
+            'c' if self.first() == '"' || self.is_raw_string_start() => {
+                self.c_or_byte_string(StringPrefix::CStr)
+            }
+
+            c if is_id_start(c) => self.ident(),
+
+            c @ '0'..='9' => {
+                let literal_kind = crate::literals::number(self, c);
+                let suffix_start = self.len_consumed();
+                self.eat_literal_suffix();
+                TokenKind::Literal { kind: literal_kind, suffix_start }
+            }
+
+            ';' => TokenKind::Semi,
+            ',' => TokenKind::Comma,
+            '.' => TokenKind::Dot,
+            '(' => TokenKind::OpenParen,
+            ')' => TokenKind::CloseParen,
+            '{' => TokenKind::OpenBrace,
+            '}' => TokenKind::CloseBrace,
+            '[' => TokenKind::OpenBracket,
+            ']' => TokenKind::CloseBracket,
+            '@' => TokenKind::At,
+            '#' => TokenKind::Pound,
+            '~' => TokenKind::Tilde,
+            '?' => TokenKind::Question,
+            ':' => TokenKind::Colon,
+            '$' => TokenKind::Dollar,
+            '=' => TokenKind::Eq,
+            '!' => TokenKind::Bang,
+            '<' => TokenKind::Lt,
+            '>' => TokenKind::Gt,
+            '-' => TokenKind::Minus,
+            '&' => TokenKind::And,
+            '|' => TokenKind::Or,
+            '+' => TokenKind::Plus,
+            '*' => TokenKind::Star,
+            '^' => TokenKind::Caret,
+            '%' => TokenKind::Percent,
+
+            '\'' => crate::literals::lifetime_or_char(self),
+            '"' => {
+                let terminated = crate::literals::double_quoted_string(self);
+                let suffix_start = self.len_consumed();
+                if terminated {
+                    self.eat_literal_suffix();
+                }
+                let kind = LiteralKind::Str { terminated };
+                TokenKind::Literal { kind, suffix_start }
+            }
+
+            _ => TokenKind::Unknown,
+        };
+        let res = Token::new(token_kind, self.len_consumed());
+        self.reset_len_consumed();
+        res
+    }
+
+    fn line_comment(&mut self) -> TokenKind {
+        debug_assert!(self.prev() == '/' && self.first() == '/');
+        self.bump();
+        self.eat_while(|c| c != '\n');
+        TokenKind::LineComment
+    }
+
+    fn block_comment(&mut self) -> TokenKind {
+        debug_assert!(self.prev() == '/' && self.first() == '*');
+        self.bump();
+        let mut depth = 1usize;
+        while let Some(c) = self.bump() {
+            match c {
+                '/' if self.first() == '*' => {
+                    self.bump();
+                    depth += 1;
+                }
+                '*' if self.first() == '/' => {
+                    self.bump();
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => (),
+            }
+        }
+        TokenKind::BlockComment { terminated: depth == 0 }
+    }
+
+    fn whitespace(&mut self) -> TokenKind {
+        debug_assert!(self.prev().is_whitespace());
+        self.eat_while(char::is_whitespace);
+        TokenKind::Whitespace
+    }
+
+    /// Whether the cursor (positioned right after a `b`) is looking at a
+    /// `b'..'`, `b"..."`, or `br"..."`/`br#"..."#` form.
+    fn is_byte_or_c_string_start(&self) -> bool {
+        matches!(self.first(), '\'' | '"') || self.is_raw_string_start()
+    }
+
+    /// Whether the cursor (positioned right after a `b`/`c`) is looking at a
+    /// `r"..."`/`r#"..."#` raw string, as opposed to an identifier that
+    /// merely continues with `r` (e.g. `brown`, `crate`).
+    fn is_raw_string_start(&self) -> bool {
+        self.first() == 'r' && matches!(self.second(), '"' | '#')
+    }
+
+    fn ident(&mut self) -> TokenKind {
+        debug_assert!(is_id_start(self.prev()));
+        self.eat_while(is_id_continue);
+        TokenKind::Ident
+    }
+
+    /// Handles the `r"..."`/`r#"..."#` raw string forms, and the `r#ident`
+    /// raw identifier form, once the tokenizer has bumped past the leading `r`.
+    fn raw_ident_or_string(&mut self) -> TokenKind {
+        debug_assert!(self.prev() == 'r');
+        if self.first() == '#' && is_id_start(self.second()) {
+            self.bump();
+            self.eat_while(is_id_continue);
+            return TokenKind::RawIdent;
+        }
+        let (n_hashes, err, contents) = crate::literals::raw_double_quoted_string(self, 1);
+        let suffix_start = self.len_consumed();
+        if err.is_none() {
+            self.eat_literal_suffix();
+        }
+        let kind = LiteralKind::RawStr { n_hashes, err, contents };
+        TokenKind::Literal { kind, suffix_start }
+    }
+
+    /// Handles the `b`/`c` prefixed char/string/raw-string forms, once the
+    /// tokenizer has bumped past the prefix letter itself.
+    fn c_or_byte_string(&mut self, prefix: StringPrefix) -> TokenKind {
+        crate::literals::c_or_byte_string(self, prefix)
+    }
+}
@@ -5,9 +5,9 @@ use std::convert::TryFrom;
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum LiteralKind {
     /// "12_u8", "0o100", "0b120i99"
-    Int { base: Base, empty_int: bool },
+    Int { base: Base, empty_int: bool, first_invalid_digit_offset: Option<usize> },
     /// "12.34f32", "0b100.100"
-    Float { base: Base, empty_exponent: bool },
+    Float { base: Base, empty_exponent: bool, first_invalid_digit_offset: Option<usize> },
     /// "'a'", "'\\'", "'''", "';"
     Char { terminated: bool },
     /// "b'a'", "b'\\'", "b'''", "b';"
@@ -16,10 +16,27 @@ pub enum LiteralKind {
     Str { terminated: bool },
     /// "b"abc"", "b"abc"
     ByteStr { terminated: bool },
+    /// "c"abc"", "c"abc"
+    CStr { terminated: bool },
     /// "r"abc"", "r#"abc"#", "r####"ab"###"c"####", "r#"a"
-    RawStr { n_hashes: u16, err: Option<RawStrError> },
+    RawStr { n_hashes: u16, err: Option<RawStrError>, contents: RawStrContents },
     /// "br"abc"", "br#"abc"#", "br####"ab"###"c"####", "br#"a"
-    RawByteStr { n_hashes: u16, err: Option<RawStrError> },
+    RawByteStr { n_hashes: u16, err: Option<RawStrError>, contents: RawStrContents },
+    /// "cr"abc"", "cr#"abc"#", "cr####"ab"###"c"####", "cr#"a"
+    RawCStr { n_hashes: u16, err: Option<RawStrError>, contents: RawStrContents },
+}
+
+/// Byte offsets, relative to the `r` that starts a raw string/raw byte
+/// string/raw C string, delimiting the literal's content: after the opening
+/// `"` (and any `#`s), up to (but not including) the closing `"`.
+///
+/// This lets callers slice out the raw body verbatim without re-counting
+/// the opening hashes themselves. Meaningless (both `0`) if the literal
+/// never found an opening quote to begin with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RawStrContents {
+    pub start_offset: usize,
+    pub end_offset: usize,
 }
 
 /// Base of numeric literal encoding according to its prefix.
@@ -35,6 +52,18 @@ pub enum Base {
     Decimal,
 }
 
+impl Base {
+    /// The radix of digits that are legal for this base.
+    fn radix(self) -> u32 {
+        match self {
+            Base::Binary => 2,
+            Base::Octal => 8,
+            Base::Hexadecimal => 16,
+            Base::Decimal => 10,
+        }
+    }
+}
+
 /// Error produced validating a raw string. Represents cases like:
 /// - `r##~"abcde"##`: `InvalidStarter`
 /// - `r###"abcde"##`: `NoTerminator { expected: 3, found: 2, possible_terminator_offset: Some(11)`
@@ -54,22 +83,29 @@ pub enum RawStrError {
 pub(crate) fn number(cursor: &mut Cursor, first_digit: char) -> LiteralKind {
     debug_assert!('0' <= cursor.prev() && cursor.prev() <= '9');
     let mut base = Base::Decimal;
+    let mut first_invalid_digit_offset = None;
     if first_digit == '0' {
         // Attempt to parse encoding base.
         let has_digits = match cursor.first() {
             'b' => {
                 base = Base::Binary;
                 cursor.bump();
-                cursor.eat_decimal_digits()
+                eat_digits_tracking_invalid(cursor, base, &mut first_invalid_digit_offset)
             }
             'o' => {
                 base = Base::Octal;
                 cursor.bump();
-                cursor.eat_decimal_digits()
+                eat_digits_tracking_invalid(cursor, base, &mut first_invalid_digit_offset)
             }
             'x' => {
                 base = Base::Hexadecimal;
                 cursor.bump();
+                // Unlike the binary/octal cases, `eat_hexadecimal_digits` only
+                // ever consumes digits that are already legal for this base
+                // (plus `_`) and stops cleanly at the first character that
+                // isn't, so there is no out-of-range digit for it to recover
+                // past here; `first_invalid_digit_offset` is always `None`
+                // for hexadecimal literals, same as for decimal ones.
                 cursor.eat_hexadecimal_digits()
             }
             // Not a base prefix.
@@ -78,12 +114,14 @@ pub(crate) fn number(cursor: &mut Cursor, first_digit: char) -> LiteralKind {
                 true
             }
             // Just a 0.
-            _ => return LiteralKind::Int { base, empty_int: false },
+            _ => {
+                return LiteralKind::Int { base, empty_int: false, first_invalid_digit_offset };
+            }
         };
         // Base prefix was provided, but there were no digits
         // after it, e.g. "0x".
         if !has_digits {
-            return LiteralKind::Int { base, empty_int: true };
+            return LiteralKind::Int { base, empty_int: true, first_invalid_digit_offset };
         }
     } else {
         // No base prefix, parse number in the usual way.
@@ -99,7 +137,7 @@ pub(crate) fn number(cursor: &mut Cursor, first_digit: char) -> LiteralKind {
             // with a number
             cursor.bump();
             let mut empty_exponent = false;
-            if cursor.first().is_digit(10) {
+            if cursor.first().is_ascii_digit() {
                 cursor.eat_decimal_digits();
                 match cursor.first() {
                     'e' | 'E' => {
@@ -109,17 +147,45 @@ pub(crate) fn number(cursor: &mut Cursor, first_digit: char) -> LiteralKind {
                     _ => (),
                 }
             }
-            LiteralKind::Float { base, empty_exponent }
+            LiteralKind::Float { base, empty_exponent, first_invalid_digit_offset }
         }
         'e' | 'E' => {
             cursor.bump();
             let empty_exponent = !cursor.eat_float_exponent();
-            LiteralKind::Float { base, empty_exponent }
+            LiteralKind::Float { base, empty_exponent, first_invalid_digit_offset }
         }
-        _ => LiteralKind::Int { base, empty_int: false },
+        _ => LiteralKind::Int { base, empty_int: false, first_invalid_digit_offset },
     }
 }
 
+/// Eats the decimal digit run following a `0b`/`0o` prefix, the same way
+/// `Cursor::eat_decimal_digits` does for recovery, while additionally
+/// recording in `first_invalid_digit_offset` the offset (relative to the
+/// start of the literal) of the first digit that is out of range for `base`.
+fn eat_digits_tracking_invalid(
+    cursor: &mut Cursor,
+    base: Base,
+    first_invalid_digit_offset: &mut Option<usize>,
+) -> bool {
+    let mut has_digits = false;
+    loop {
+        match cursor.first() {
+            '_' => {
+                cursor.bump();
+            }
+            digit @ '0'..='9' => {
+                has_digits = true;
+                if first_invalid_digit_offset.is_none() && !digit.is_digit(base.radix()) {
+                    *first_invalid_digit_offset = Some(cursor.len_consumed());
+                }
+                cursor.bump();
+            }
+            _ => break,
+        }
+    }
+    has_digits
+}
+
 pub(crate) fn lifetime_or_char(cursor: &mut Cursor) -> TokenKind {
     debug_assert!(cursor.prev() == '\'');
 
@@ -130,7 +196,7 @@ pub(crate) fn lifetime_or_char(cursor: &mut Cursor) -> TokenKind {
         // If the first symbol is valid for identifier, it can be a lifetime.
         // Also check if it's a number for a better error reporting (so '0 will
         // be reported as invalid lifetime and not as unterminated char literal).
-        is_id_start(cursor.first()) || cursor.first().is_digit(10)
+        is_id_start(cursor.first()) || cursor.first().is_ascii_digit()
     };
 
     if !can_be_a_lifetime {
@@ -146,7 +212,7 @@ pub(crate) fn lifetime_or_char(cursor: &mut Cursor) -> TokenKind {
     // Either a lifetime or a character literal with
     // length greater than 1.
 
-    let starts_with_number = cursor.first().is_digit(10);
+    let starts_with_number = cursor.first().is_ascii_digit();
 
     // Skip the literal contents.
     // First symbol can be a number (which isn't a valid identifier start),
@@ -227,23 +293,27 @@ pub(crate) fn double_quoted_string(cursor: &mut Cursor) -> bool {
     false
 }
 
-/// Eats the double-quoted string and returns `n_hashes` and an error if encountered.
+/// Eats the double-quoted string and returns `n_hashes`, an error if
+/// encountered, and the [`RawStrContents`] offsets of its body.
 pub(crate) fn raw_double_quoted_string(
     cursor: &mut Cursor,
     prefix_len: usize,
-) -> (u16, Option<RawStrError>) {
+) -> (u16, Option<RawStrError>, RawStrContents) {
     // Wrap the actual function to handle the error with too many hashes.
     // This way, it eats the whole raw string.
-    let (n_hashes, err) = raw_string_unvalidated(cursor, prefix_len);
+    let (n_hashes, err, contents) = raw_string_unvalidated(cursor, prefix_len);
     // Only up to 65535 `#`s are allowed in raw strings
     match u16::try_from(n_hashes) {
-        Ok(num) => (num, err),
+        Ok(num) => (num, err, contents),
         // We lie about the number of hashes here :P
-        Err(_) => (0, Some(RawStrError::TooManyDelimiters { found: n_hashes })),
+        Err(_) => (0, Some(RawStrError::TooManyDelimiters { found: n_hashes }), contents),
     }
 }
 
-fn raw_string_unvalidated(cursor: &mut Cursor, prefix_len: usize) -> (usize, Option<RawStrError>) {
+fn raw_string_unvalidated(
+    cursor: &mut Cursor,
+    prefix_len: usize,
+) -> (usize, Option<RawStrError>, RawStrContents) {
     debug_assert!(cursor.prev() == 'r');
     let start_pos = cursor.len_consumed();
     let mut possible_terminator_offset = None;
@@ -256,20 +326,27 @@ fn raw_string_unvalidated(cursor: &mut Cursor, prefix_len: usize) -> (usize, Opt
         cursor.bump();
     }
     let n_start_hashes = eaten;
+    let no_contents = RawStrContents { start_offset: 0, end_offset: 0 };
 
     // Check that string is started.
     match cursor.bump() {
         Some('"') => (),
         c => {
             let c = c.unwrap_or(EOF_CHAR);
-            return (n_start_hashes, Some(RawStrError::InvalidStarter { bad_char: c }));
+            return (n_start_hashes, Some(RawStrError::InvalidStarter { bad_char: c }), no_contents);
         }
     }
+    // The content starts right after the opening quote; `+ 1` accounts for
+    // `r` itself, which is not included in `len_consumed() - start_pos`.
+    let start_offset = cursor.len_consumed() - start_pos + 1;
 
     // Skip the string contents and on each '#' character met, check if this is
     // a raw string termination.
     loop {
         cursor.eat_while(|c| c != '"');
+        // The end of the scanned contents so far, i.e. right before whatever
+        // `"` we are about to check below (or before EOF).
+        let end_offset = cursor.len_consumed() - start_pos + 1;
 
         if cursor.is_eof() {
             return (
@@ -279,6 +356,7 @@ fn raw_string_unvalidated(cursor: &mut Cursor, prefix_len: usize) -> (usize, Opt
                     found: max_hashes,
                     possible_terminator_offset,
                 }),
+                RawStrContents { start_offset, end_offset },
             );
         }
 
@@ -297,7 +375,7 @@ fn raw_string_unvalidated(cursor: &mut Cursor, prefix_len: usize) -> (usize, Opt
         }
 
         if n_end_hashes == n_start_hashes {
-            return (n_start_hashes, None);
+            return (n_start_hashes, None, RawStrContents { start_offset, end_offset });
         } else if n_end_hashes > max_hashes {
             // Keep track of possible terminators to give a hint about
             // where there might be a missing terminator
@@ -307,3 +385,155 @@ fn raw_string_unvalidated(cursor: &mut Cursor, prefix_len: usize) -> (usize, Opt
         }
     }
 }
+
+/// Which string-literal family a `b`/`c` prefix introduces. The two
+/// prefixes scan identically other than `b` additionally allowing a
+/// single-quoted `byte` literal (a C string is always a nul-terminated
+/// sequence, never a single byte), so the tokenizer's `'b'`/`'c'` match arms
+/// both dispatch through [`c_or_byte_string`] instead of duplicating it.
+#[derive(Clone, Copy)]
+pub(crate) enum StringPrefix {
+    /// `b'a'`, `b"abc"`, `br"abc"`
+    Byte,
+    /// `c"abc"`, `cr"abc"`
+    CStr,
+}
+
+/// Lexes whatever follows a `b`/`c` prefix once the tokenizer has bumped
+/// past the prefix letter itself: a single-quoted `byte` literal (`b` only),
+/// a double-quoted string, or a raw string. The tokenizer's entry point
+/// calls this the same way it calls `lifetime_or_char` for a bare `'`.
+pub(crate) fn c_or_byte_string(cursor: &mut Cursor, prefix: StringPrefix) -> TokenKind {
+    match cursor.first() {
+        '\'' if matches!(prefix, StringPrefix::Byte) => {
+            cursor.bump();
+            let terminated = single_quoted_string(cursor);
+            let suffix_start = cursor.len_consumed();
+            if terminated {
+                cursor.eat_literal_suffix();
+            }
+            TokenKind::Literal { kind: LiteralKind::Byte { terminated }, suffix_start }
+        }
+        '"' => {
+            cursor.bump();
+            let terminated = double_quoted_string(cursor);
+            let suffix_start = cursor.len_consumed();
+            if terminated {
+                cursor.eat_literal_suffix();
+            }
+            let kind = match prefix {
+                StringPrefix::Byte => LiteralKind::ByteStr { terminated },
+                StringPrefix::CStr => LiteralKind::CStr { terminated },
+            };
+            TokenKind::Literal { kind, suffix_start }
+        }
+        'r' => {
+            cursor.bump();
+            // `prefix_len` is 2 for both `br"` and `cr"`.
+            let (n_hashes, err, contents) = raw_double_quoted_string(cursor, 2);
+            let suffix_start = cursor.len_consumed();
+            if err.is_none() {
+                cursor.eat_literal_suffix();
+            }
+            let kind = match prefix {
+                StringPrefix::Byte => LiteralKind::RawByteStr { n_hashes, err, contents },
+                StringPrefix::CStr => LiteralKind::RawCStr { n_hashes, err, contents },
+            };
+            TokenKind::Literal { kind, suffix_start }
+        }
+        _ => unreachable!("c_or_byte_string is only called when first() is `'`, `\"`, or `r`"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_number(src: &str, expected: LiteralKind) {
+        let mut cursor = Cursor::new(src);
+        let first_digit = cursor.bump().unwrap();
+        assert_eq!(number(&mut cursor, first_digit), expected);
+    }
+
+    #[test]
+    fn first_invalid_digit_offset_binary() {
+        // `2` at index 3 is out of range for base 2.
+        check_number(
+            "0b120",
+            LiteralKind::Int { base: Base::Binary, empty_int: false, first_invalid_digit_offset: Some(3) },
+        );
+    }
+
+    #[test]
+    fn first_invalid_digit_offset_octal() {
+        // `8` at index 4 is out of range for base 8.
+        check_number(
+            "0o1289",
+            LiteralKind::Int { base: Base::Octal, empty_int: false, first_invalid_digit_offset: Some(4) },
+        );
+    }
+
+    #[test]
+    fn first_invalid_digit_offset_hexadecimal_is_always_none() {
+        // `z` isn't a digit at all, so it's left for the suffix, not recorded
+        // as an invalid digit.
+        check_number(
+            "0x1z",
+            LiteralKind::Int { base: Base::Hexadecimal, empty_int: false, first_invalid_digit_offset: None },
+        );
+    }
+
+    fn check_raw_str(
+        src: &str,
+        bytes_before_r: usize,
+        prefix_len: usize,
+        expected: (usize, Option<RawStrError>, RawStrContents),
+    ) {
+        let mut cursor = Cursor::new(src);
+        for _ in 0..bytes_before_r {
+            cursor.bump(); // any `b`/`c` prefix byte
+        }
+        cursor.bump(); // the `r`
+        assert_eq!(raw_string_unvalidated(&mut cursor, prefix_len), expected);
+    }
+
+    #[test]
+    fn raw_str_contents_terminated() {
+        // `r###"abc"###`
+        check_raw_str(
+            "r###\"abc\"###",
+            0,
+            1,
+            (3, None, RawStrContents { start_offset: 5, end_offset: 8 }),
+        );
+    }
+
+    #[test]
+    fn raw_str_contents_unterminated() {
+        // `r"abc` (never closed)
+        check_raw_str(
+            "r\"abc",
+            0,
+            1,
+            (
+                0,
+                Some(RawStrError::NoTerminator { expected: 0, found: 0, possible_terminator_offset: None }),
+                RawStrContents { start_offset: 2, end_offset: 5 },
+            ),
+        );
+    }
+
+    #[test]
+    fn raw_str_contents_with_byte_or_c_prefix() {
+        // `br#"abc"#`. `br"..."`/`cr"..."` share the same offset math as
+        // bare `r"..."`; the extra prefix byte is already consumed before
+        // this function is ever reached, so offsets are still relative to
+        // the `r`.
+        check_raw_str(
+            "br#\"abc\"#",
+            1,
+            2,
+            (1, None, RawStrContents { start_offset: 3, end_offset: 6 }),
+        );
+    }
+}
@@ -0,0 +1,367 @@
+//! Validates and unescapes `char`, `str`, `byte` and `byte str` literals, once
+//! the tokenizer has already classified them via [`crate::LiteralKind`].
+//!
+//! This module does not re-scan the raw literal (that is the tokenizer's
+//! job); it only interprets the already-delimited contents, so callers pass
+//! in the literal text *without* surrounding quotes or prefix.
+
+use std::ops::Range;
+use std::str::Chars;
+
+/// Errors that can occur while unescaping a `char`/`str`/`byte`/`byte str`
+/// literal's contents. The tokenizer deliberately defers all of this
+/// validation, since it only needs to find the end of the literal.
+#[derive(Debug, PartialEq, Eq)]
+pub enum EscapeError {
+    /// Expected 1 char, but 0 were found.
+    ZeroChars,
+    /// Expected 1 char, but more than 1 were found.
+    MoreThanOneChar,
+
+    /// Escaped '\' character without continuation.
+    LoneSlash,
+    /// Invalid escape character (e.g. '\z').
+    InvalidEscape,
+    /// Raw '\r' encountered.
+    BareCarriageReturn,
+    /// Unescaped character that was expected to be escaped (e.g. raw '\t').
+    EscapeOnlyChar,
+    /// Unescaped quote that matches the literal's own delimiter, e.g. an
+    /// unescaped `"` inside a `"..."` literal.
+    UnescapedQuote,
+
+    /// Numeric character escape is too short (e.g. '\x1').
+    TooShortHexEscape,
+    /// Invalid character in numeric escape (e.g. '\xz').
+    InvalidCharInHexEscape,
+    /// Character code in numeric escape is non-ascii, e.g. `'\xFF'`. Only a
+    /// `byte`/`byte str` literal may use the full `0x00..=0xFF` range; a
+    /// `char`/`str` literal's `\x` escapes are ASCII-only.
+    OutOfRangeHexEscape,
+
+    /// '\u' not followed by '{'.
+    NoBraceInUnicodeEscape,
+    /// Non-hexadecimal value in '\u{..}'.
+    InvalidCharInUnicodeEscape,
+    /// '\u{}'
+    EmptyUnicodeEscape,
+    /// No closing brace in '\u{..}' (e.g. '\u{12').
+    UnclosedUnicodeEscape,
+    /// '\u{_12}'
+    LeadingUnderscoreUnicodeEscape,
+    /// More than 6 characters in '\u{..}'.
+    OverlongUnicodeEscape,
+    /// Invalid in-bound unicode character code, e.g. '\u{DFFF}'.
+    LoneSurrogateUnicodeEscape,
+    /// Out of bounds unicode character code, e.g. '\u{FFFFFF}'.
+    OutOfRangeUnicodeEscape,
+
+    /// Unicode escape code in byte literal.
+    UnicodeEscapeInByte,
+    /// Non-ascii character in byte literal.
+    NonAsciiCharInByte,
+}
+
+/// Whether unescaping targets a `char`-like or `u8`-like literal. This
+/// decides the ascii-only restrictions that apply to byte literals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Char,
+    Byte,
+}
+
+impl Mode {
+    fn allow_unicode(self) -> bool {
+        match self {
+            Mode::Char => true,
+            Mode::Byte => false,
+        }
+    }
+}
+
+/// Takes the contents of a char literal (without quotes), and returns an
+/// unescaped char or an error.
+pub fn unescape_char(literal_text: &str) -> Result<char, EscapeError> {
+    unescape_char_or_byte(&mut literal_text.chars(), Mode::Char)
+}
+
+/// Takes the contents of a byte literal (without quotes), and returns an
+/// unescaped byte or an error.
+pub fn unescape_byte(literal_text: &str) -> Result<u8, EscapeError> {
+    unescape_char_or_byte(&mut literal_text.chars(), Mode::Byte).map(|c| c as u8)
+}
+
+/// Takes the contents of a string literal (without quotes) and produces a
+/// sequence of escaped characters, or errors, via `callback`. Each callback
+/// invocation is given the byte range (relative to `literal_text`) that the
+/// character or error came from.
+pub fn unescape_str(
+    literal_text: &str,
+    callback: &mut dyn FnMut(Range<usize>, Result<char, EscapeError>),
+) {
+    unescape_str_or_byte_str(literal_text, Mode::Char, callback);
+}
+
+/// Takes the contents of a byte string literal (without quotes) and produces
+/// a sequence of unescaped bytes, or errors, via `callback`.
+pub fn unescape_byte_str(
+    literal_text: &str,
+    callback: &mut dyn FnMut(Range<usize>, Result<u8, EscapeError>),
+) {
+    unescape_str_or_byte_str(literal_text, Mode::Byte, &mut |range, res| {
+        callback(range, res.map(|c| c as u8));
+    });
+}
+
+fn unescape_char_or_byte(chars: &mut Chars<'_>, mode: Mode) -> Result<char, EscapeError> {
+    let c = chars.next().ok_or(EscapeError::ZeroChars)?;
+    let res = match c {
+        '\\' => scan_escape(chars, mode),
+        '\n' | '\t' | '\'' => Err(EscapeError::EscapeOnlyChar),
+        '\r' => Err(EscapeError::BareCarriageReturn),
+        _ if !mode.allow_unicode() && !c.is_ascii() => Err(EscapeError::NonAsciiCharInByte),
+        _ => Ok(c),
+    }?;
+    if chars.next().is_some() {
+        return Err(EscapeError::MoreThanOneChar);
+    }
+    Ok(res)
+}
+
+fn unescape_str_or_byte_str(
+    literal_text: &str,
+    mode: Mode,
+    callback: &mut dyn FnMut(Range<usize>, Result<char, EscapeError>),
+) {
+    let mut chars = literal_text.chars();
+    while let Some(first) = chars.next() {
+        let start = literal_text.len() - chars.as_str().len() - first.len_utf8();
+        if first == '\\' && chars.clone().next() == Some('\n') {
+            // String continuation: a line-ending backslash followed by a
+            // newline, which swallows the newline and any leading
+            // whitespace on the next line.
+            chars.next();
+            skip_ascii_whitespace(&mut chars);
+            continue;
+        }
+        let result = match first {
+            '\\' => scan_escape(&mut chars, mode),
+            '"' => Err(EscapeError::UnescapedQuote),
+            '\r' => Err(EscapeError::BareCarriageReturn),
+            _ if !mode.allow_unicode() && !first.is_ascii() => Err(EscapeError::NonAsciiCharInByte),
+            _ => Ok(first),
+        };
+        let end = literal_text.len() - chars.as_str().len();
+        callback(start..end, result);
+    }
+}
+
+/// Skips the leading ascii whitespace on the line that follows a
+/// `\`-newline string continuation.
+fn skip_ascii_whitespace(chars: &mut Chars<'_>) {
+    let tail = chars.as_str();
+    let first_non_space = tail
+        .bytes()
+        .position(|b| b != b' ' && b != b'\t' && b != b'\n' && b != b'\r')
+        .unwrap_or(tail.len());
+    *chars = tail[first_non_space..].chars();
+}
+
+/// Interprets the escape sequence that follows a backslash (the backslash
+/// itself must already be consumed from `chars`).
+fn scan_escape(chars: &mut Chars<'_>, mode: Mode) -> Result<char, EscapeError> {
+    let res = match chars.next().ok_or(EscapeError::LoneSlash)? {
+        '"' => '"',
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+        '\\' => '\\',
+        '\'' => '\'',
+        '0' => '\0',
+
+        'x' => {
+            let hi = chars.next().ok_or(EscapeError::TooShortHexEscape)?;
+            let hi = hi.to_digit(16).ok_or(EscapeError::InvalidCharInHexEscape)?;
+            let lo = chars.next().ok_or(EscapeError::TooShortHexEscape)?;
+            let lo = lo.to_digit(16).ok_or(EscapeError::InvalidCharInHexEscape)?;
+            let value = hi * 16 + lo;
+
+            // In a char/str literal, `\x` only reaches ASCII; full-range
+            // bytes need `\u{..}` or, for a byte literal, `\xFF` itself.
+            if mode.allow_unicode() && value > 0x7F {
+                return Err(EscapeError::OutOfRangeHexEscape);
+            }
+            value as u8 as char
+        }
+
+        'u' => {
+            if !mode.allow_unicode() {
+                return Err(EscapeError::UnicodeEscapeInByte);
+            }
+            if chars.next() != Some('{') {
+                return Err(EscapeError::NoBraceInUnicodeEscape);
+            }
+
+            let mut n_digits = 0;
+            let mut value: u32 = 0;
+            loop {
+                match chars.next() {
+                    None => return Err(EscapeError::UnclosedUnicodeEscape),
+                    Some('_') if n_digits == 0 => {
+                        return Err(EscapeError::LeadingUnderscoreUnicodeEscape);
+                    }
+                    Some('_') => continue,
+                    Some('}') => {
+                        if n_digits == 0 {
+                            return Err(EscapeError::EmptyUnicodeEscape);
+                        }
+                        if n_digits > 6 {
+                            return Err(EscapeError::OverlongUnicodeEscape);
+                        }
+                        break std::char::from_u32(value).ok_or(if value > 0x10FFFF {
+                            EscapeError::OutOfRangeUnicodeEscape
+                        } else {
+                            EscapeError::LoneSurrogateUnicodeEscape
+                        })?;
+                    }
+                    Some(c) => {
+                        let digit = c.to_digit(16).ok_or(EscapeError::InvalidCharInUnicodeEscape)?;
+                        n_digits += 1;
+                        if n_digits > 6 {
+                            continue;
+                        }
+                        value = value * 16 + digit;
+                    }
+                }
+            }
+        }
+
+        _ => return Err(EscapeError::InvalidEscape),
+    };
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unescape_char_bad() {
+        fn check(literal_text: &str, expected_error: EscapeError) {
+            assert_eq!(unescape_char(literal_text), Err(expected_error));
+        }
+
+        check("", EscapeError::ZeroChars);
+        check("ab", EscapeError::MoreThanOneChar);
+
+        check(r"\", EscapeError::LoneSlash);
+        check(r"\v", EscapeError::InvalidEscape);
+
+        check("\n", EscapeError::EscapeOnlyChar);
+        check("\t", EscapeError::EscapeOnlyChar);
+        check("'", EscapeError::EscapeOnlyChar);
+        check("\r", EscapeError::BareCarriageReturn);
+
+        check(r"\x", EscapeError::TooShortHexEscape);
+        check(r"\x1", EscapeError::TooShortHexEscape);
+        check(r"\xz1", EscapeError::InvalidCharInHexEscape);
+        check(r"\x1z", EscapeError::InvalidCharInHexEscape);
+        check(r"\xff", EscapeError::OutOfRangeHexEscape);
+        check(r"\xFF", EscapeError::OutOfRangeHexEscape);
+
+        check(r"\u0041", EscapeError::NoBraceInUnicodeEscape);
+        check(r"\u{", EscapeError::UnclosedUnicodeEscape);
+        check(r"\u{41", EscapeError::UnclosedUnicodeEscape);
+        check(r"\u{}", EscapeError::EmptyUnicodeEscape);
+        check(r"\u{_41}", EscapeError::LeadingUnderscoreUnicodeEscape);
+        check(r"\u{zz}", EscapeError::InvalidCharInUnicodeEscape);
+        check(r"\u{1000000}", EscapeError::OverlongUnicodeEscape);
+        check(r"\u{110000}", EscapeError::OutOfRangeUnicodeEscape);
+        check(r"\u{D800}", EscapeError::LoneSurrogateUnicodeEscape);
+    }
+
+    #[test]
+    fn test_unescape_char_good() {
+        fn check(literal_text: &str, expected_char: char) {
+            assert_eq!(unescape_char(literal_text), Ok(expected_char));
+        }
+
+        check("a", 'a');
+        check(r"\n", '\n');
+        check(r"\r", '\r');
+        check(r"\t", '\t');
+        check(r"\\", '\\');
+        check(r"\'", '\'');
+        check("\\\"", '"');
+        check(r"\0", '\0');
+        check(r"\x61", 'a');
+        check(r"\u{7FFF}", '\u{7FFF}');
+        check(r"\u{000041}", 'A');
+    }
+
+    #[test]
+    fn test_unescape_byte_bad() {
+        fn check(literal_text: &str, expected_error: EscapeError) {
+            assert_eq!(unescape_byte(literal_text), Err(expected_error));
+        }
+
+        check(r"\u{0}", EscapeError::UnicodeEscapeInByte);
+        check("\u{00FF}", EscapeError::NonAsciiCharInByte);
+    }
+
+    #[test]
+    fn test_unescape_byte_good() {
+        // Unlike a `char`/`str` literal, `\xFF` in a `byte`/`byte str`
+        // literal is the full-range byte value, not restricted to ASCII.
+        assert_eq!(unescape_byte(r"\xff"), Ok(0xffu8));
+        assert_eq!(unescape_byte(r"\x61"), Ok(b'a'));
+        assert_eq!(unescape_byte("a"), Ok(b'a'));
+    }
+
+    fn collect_str(literal_text: &str) -> Result<String, (Range<usize>, EscapeError)> {
+        let mut buf = Ok(String::with_capacity(literal_text.len()));
+        unescape_str(literal_text, &mut |range, c| {
+            if let Ok(b) = &mut buf {
+                match c {
+                    Ok(c) => b.push(c),
+                    Err(e) => buf = Err((range, e)),
+                }
+            }
+        });
+        buf
+    }
+
+    #[test]
+    fn test_unescape_str_good() {
+        assert_eq!(collect_str("foo").as_deref(), Ok("foo"));
+        assert_eq!(collect_str("").as_deref(), Ok(""));
+        assert_eq!(collect_str(" \t\n").as_deref(), Ok(" \t\n"));
+        assert_eq!(collect_str("thread's").as_deref(), Ok("thread's"));
+    }
+
+    #[test]
+    fn test_unescape_str_continuation() {
+        // A line-ending `\` swallows the newline and any leading
+        // whitespace (including further newlines) on the following lines.
+        assert_eq!(collect_str("hello \\\n     world").as_deref(), Ok("hello world"));
+        assert_eq!(collect_str("a\\\n\n\t b").as_deref(), Ok("ab"));
+    }
+
+    #[test]
+    fn test_unescape_str_bad() {
+        assert_eq!(collect_str("\r").unwrap_err().1, EscapeError::BareCarriageReturn);
+        assert_eq!(collect_str("a\"b").unwrap_err().1, EscapeError::UnescapedQuote);
+    }
+
+    #[test]
+    fn test_unescape_byte_str_non_ascii() {
+        let mut saw_error = false;
+        unescape_byte_str("\u{00FF}", &mut |_range, res: Result<u8, EscapeError>| {
+            if res == Err(EscapeError::NonAsciiCharInByte) {
+                saw_error = true;
+            }
+        });
+        assert!(saw_error);
+    }
+}
+